@@ -17,13 +17,28 @@
 //!     println!("{}", FOO.value);
 //! }
 //! ```
+//!
+//! [`SyncLateStatic`] offers the same pattern without the `unsafe` caveat, at the cost
+//! of an atomic state check on every access.
 #![cfg_attr(not(test), no_std)]
 
-use core::cell::UnsafeCell;
+use core::cell::{Cell, UnsafeCell};
+use core::mem::MaybeUninit;
 
 /// Static value that is manually initialized at runtime.
+///
+/// The inner storage is an `UnsafeCell<MaybeUninit<T>>` guarded by a `Cell<bool>`
+/// flag. The value is written through a shared reference, which is only legal when
+/// that memory sits inside an `UnsafeCell`, no matter how carefully callers avoid
+/// concurrent access.
+///
+/// The compiler treats `UnsafeCell<U>` as invariant in `U` regardless of what it's
+/// nested inside, so `LateStatic<T>` is invariant in `T`. [`SyncLateStatic`] is the
+/// thread-safe alternative for callers who don't need `unsafe`, but it stores its
+/// value the same way and is just as invariant in `T`.
 pub struct LateStatic<T> {
-    val: UnsafeCell<Option<T>>,
+    is_init: Cell<bool>,
+    val: UnsafeCell<MaybeUninit<T>>,
 }
 
 unsafe impl<T: Send> core::marker::Send for LateStatic<T> {}
@@ -33,7 +48,8 @@ impl<T> LateStatic<T> {
     /// Construct a LateStatic.
     pub const fn new() -> Self {
         LateStatic {
-            val: UnsafeCell::new(None),
+            is_init: Cell::new(false),
+            val: UnsafeCell::new(MaybeUninit::uninit()),
         }
     }
 
@@ -46,12 +62,11 @@ impl<T> LateStatic<T> {
     /// This is completely unsafe if there is even the slightest chance of another
     /// thread trying to dereference the variable.
     pub unsafe fn assign(instance: &LateStatic<T>, val: T) {
-        let option: &mut Option<T> = &mut *instance.val.get();
-        if option.is_some() {
+        if instance.is_init.get() {
             panic!("Second assignment to late static");
-        } else {
-            *option = Some(val);
         }
+        (*instance.val.get()).write(val);
+        instance.is_init.set(true);
     }
 
     /// Invalidate the late static by removing its inner value.
@@ -64,8 +79,8 @@ impl<T> LateStatic<T> {
         if !Self::has_value(instance) {
             panic!("Tried to clear a late static without a value");
         }
-        let option: &mut Option<T> = &mut *instance.val.get();
-        *option = None;
+        core::ptr::drop_in_place((*instance.val.get()).as_mut_ptr());
+        instance.is_init.set(false);
     }
 
     /// Whether a value is assigned to this LateStatic.
@@ -75,8 +90,100 @@ impl<T> LateStatic<T> {
     /// This is completely unsafe if there is even the slightest chance of another
     /// thread trying to dereference the variable.
     pub unsafe fn has_value(instance: &LateStatic<T>) -> bool {
-        let option: &Option<T> = &*instance.val.get();
-        option.is_some()
+        instance.is_init.get()
+    }
+
+    /// Get the contained value, initializing it with `f` on the first call.
+    ///
+    /// # Safety
+    ///
+    /// This is completely unsafe if there is even the slightest chance of another
+    /// thread trying to assign to or dereference the variable.
+    pub unsafe fn get_or_init(instance: &LateStatic<T>, f: impl FnOnce() -> T) -> &T {
+        if !Self::has_value(instance) {
+            Self::assign(instance, f());
+        }
+        instance
+    }
+
+    /// Get the contained value, initializing it with `f` on the first call, or
+    /// propagate `f`'s error if it fails.
+    ///
+    /// # Safety
+    ///
+    /// This is completely unsafe if there is even the slightest chance of another
+    /// thread trying to assign to or dereference the variable.
+    pub unsafe fn get_or_try_init<E>(
+        instance: &LateStatic<T>,
+        f: impl FnOnce() -> Result<T, E>,
+    ) -> Result<&T, E> {
+        if !Self::has_value(instance) {
+            Self::assign(instance, f()?);
+        }
+        Ok(instance)
+    }
+
+    /// Assign a value to the late static, returning it back if one was already
+    /// assigned, instead of panicking.
+    ///
+    /// # Safety
+    ///
+    /// This is completely unsafe if there is even the slightest chance of another
+    /// thread trying to assign to or dereference the variable.
+    pub unsafe fn try_assign(&self, val: T) -> Result<(), T> {
+        if self.is_init.get() {
+            return Err(val);
+        }
+        (*self.val.get()).write(val);
+        self.is_init.set(true);
+        Ok(())
+    }
+
+    /// Get a reference to the contained value, or `None` if it hasn't been assigned
+    /// yet, instead of panicking.
+    ///
+    /// # Safety
+    ///
+    /// This is completely unsafe if there is even the slightest chance of another
+    /// thread trying to assign to the variable.
+    pub unsafe fn try_get(&self) -> Option<&T> {
+        if self.is_init.get() {
+            Some(&*(*self.val.get()).as_ptr())
+        } else {
+            None
+        }
+    }
+
+    /// Get a mutable reference to the contained value, or `None` if it hasn't been
+    /// assigned yet, instead of panicking.
+    pub fn try_get_mut(&mut self) -> Option<&mut T> {
+        if self.is_init.get() {
+            Some(unsafe { &mut *(*self.val.get()).as_mut_ptr() })
+        } else {
+            None
+        }
+    }
+
+    /// Move the contained value out, leaving the late static empty, or return `None`
+    /// if it hasn't been assigned yet.
+    ///
+    /// # Safety
+    ///
+    /// This is completely unsafe if there is even the slightest chance of another
+    /// thread trying to assign to or dereference the variable.
+    pub unsafe fn take(&self) -> Option<T> {
+        if self.is_init.get() {
+            self.is_init.set(false);
+            Some((*self.val.get()).as_ptr().read())
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> Default for LateStatic<T> {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -84,23 +191,222 @@ impl<T> core::ops::Deref for LateStatic<T> {
     type Target = T;
 
     fn deref(&self) -> &T {
-        unsafe {
-            let option: &Option<T> = &*self.val.get();
-            match option {
-                Some(ref val) => val,
-                None => panic!("Dereference of late static before a value was assigned"),
-            }
+        if self.is_init.get() {
+            unsafe { &*(*self.val.get()).as_ptr() }
+        } else {
+            panic!("Dereference of late static before a value was assigned")
         }
     }
 }
 
 impl<T> core::ops::DerefMut for LateStatic<T> {
     fn deref_mut(&mut self) -> &mut T {
-        unsafe {
-            let option: &mut Option<T> = &mut *self.val.get();
-            match option {
-                Some(ref mut val) => val,
-                None => panic!("Dereference of late static before a value was assigned"),
+        if self.is_init.get() {
+            unsafe { &mut *(*self.val.get()).as_mut_ptr() }
+        } else {
+            panic!("Dereference of late static before a value was assigned")
+        }
+    }
+}
+
+impl<T> Drop for LateStatic<T> {
+    fn drop(&mut self) {
+        if self.is_init.get() {
+            unsafe {
+                core::ptr::drop_in_place((*self.val.get()).as_mut_ptr());
+            }
+        }
+    }
+}
+
+const SYNC_UNINIT: u32 = 0;
+const SYNC_WRITING: u32 = 1;
+const SYNC_INIT: u32 = 2;
+
+/// Resets a `SyncLateStatic`'s state back to `SYNC_UNINIT` when dropped, unless
+/// [`disarm`](Self::disarm) was called first.
+///
+/// Guards the `f()` call in `get_or_try_init`: if the initializer panics instead of
+/// returning, the state would otherwise stay stuck at `SYNC_WRITING` forever, and every
+/// later caller would spin-wait for a write that is never going to finish.
+struct ResetOnUnwind<'a> {
+    state: &'a core::sync::atomic::AtomicU32,
+    disarmed: bool,
+}
+
+impl<'a> ResetOnUnwind<'a> {
+    fn disarm(mut self) {
+        self.disarmed = true;
+    }
+}
+
+impl<'a> Drop for ResetOnUnwind<'a> {
+    fn drop(&mut self) {
+        if !self.disarmed {
+            self.state
+                .store(SYNC_UNINIT, core::sync::atomic::Ordering::Release);
+        }
+    }
+}
+
+/// Static value that is initialized at runtime and is safe to assign from multiple
+/// threads.
+///
+/// Unlike [`LateStatic`], initialization is guarded by an atomic state machine instead
+/// of relying on the caller to rule out concurrent access, so `set`/`get`/`has_value`
+/// are all safe. Only one caller ever wins the race to write the value; the others
+/// either learn that the static was already initialized or wait for the winner to
+/// finish writing.
+pub struct SyncLateStatic<T> {
+    val: core::cell::UnsafeCell<core::mem::MaybeUninit<T>>,
+    state: core::sync::atomic::AtomicU32,
+}
+
+unsafe impl<T: Send> core::marker::Send for SyncLateStatic<T> {}
+unsafe impl<T: Send + Sync> core::marker::Sync for SyncLateStatic<T> {}
+
+impl<T> SyncLateStatic<T> {
+    /// Construct a SyncLateStatic.
+    pub const fn new() -> Self {
+        SyncLateStatic {
+            val: core::cell::UnsafeCell::new(core::mem::MaybeUninit::uninit()),
+            state: core::sync::atomic::AtomicU32::new(SYNC_UNINIT),
+        }
+    }
+
+    /// Assign a value to the late static.
+    ///
+    /// Safe to call concurrently: only one caller ever succeeds, and it returns
+    /// `Ok(())`. Every other caller gets back `Err(val)` with the value it tried to
+    /// assign once the winner has finished writing.
+    ///
+    /// Unlike `get_or_try_init`, this never runs arbitrary caller code between
+    /// reserving the slot and filling it in, so there is nothing here that can unwind
+    /// and leave the state machine stuck at `SYNC_WRITING`.
+    pub fn set(&self, val: T) -> Result<(), T> {
+        use core::sync::atomic::Ordering;
+        match self.state.compare_exchange(
+            SYNC_UNINIT,
+            SYNC_WRITING,
+            Ordering::Acquire,
+            Ordering::Relaxed,
+        ) {
+            Ok(_) => {
+                unsafe {
+                    (*self.val.get()).write(val);
+                }
+                self.state.store(SYNC_INIT, Ordering::Release);
+                Ok(())
+            }
+            Err(_) => loop {
+                match self.state.load(Ordering::Acquire) {
+                    SYNC_INIT => return Err(val),
+                    SYNC_WRITING => core::hint::spin_loop(),
+                    // A failed `get_or_try_init` released the slot again; race for it.
+                    _ => return self.set(val),
+                }
+            },
+        }
+    }
+
+    /// Whether a value is assigned to this SyncLateStatic.
+    pub fn has_value(&self) -> bool {
+        self.state.load(core::sync::atomic::Ordering::Acquire) == SYNC_INIT
+    }
+
+    /// Get a reference to the contained value, or `None` if it hasn't been assigned
+    /// yet.
+    pub fn get(&self) -> Option<&T> {
+        if self.state.load(core::sync::atomic::Ordering::Acquire) == SYNC_INIT {
+            Some(unsafe { &*(*self.val.get()).as_ptr() })
+        } else {
+            None
+        }
+    }
+
+    /// Get the contained value, initializing it with `f` on the first call.
+    ///
+    /// If multiple threads call this concurrently on an uninitialized static, exactly
+    /// one of them runs `f`; the others wait for it to finish and then observe the
+    /// value it produced.
+    pub fn get_or_init(&self, f: impl FnOnce() -> T) -> &T {
+        match self.get_or_try_init(|| Ok::<T, core::convert::Infallible>(f())) {
+            Ok(val) => val,
+            Err(infallible) => match infallible {},
+        }
+    }
+
+    /// Get the contained value, initializing it with `f` on the first call, or
+    /// propagate `f`'s error if it fails.
+    ///
+    /// If `f` fails, or panics and unwinds, the static is left uninitialized so a later
+    /// call can retry.
+    pub fn get_or_try_init<E>(&self, f: impl FnOnce() -> Result<T, E>) -> Result<&T, E> {
+        use core::sync::atomic::Ordering;
+        loop {
+            match self.state.compare_exchange(
+                SYNC_UNINIT,
+                SYNC_WRITING,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => {
+                    let guard = ResetOnUnwind {
+                        state: &self.state,
+                        disarmed: false,
+                    };
+                    let result = f();
+                    guard.disarm();
+                    return match result {
+                        Ok(val) => {
+                            unsafe {
+                                (*self.val.get()).write(val);
+                            }
+                            self.state.store(SYNC_INIT, Ordering::Release);
+                            Ok(self.get().unwrap())
+                        }
+                        Err(err) => {
+                            self.state.store(SYNC_UNINIT, Ordering::Release);
+                            Err(err)
+                        }
+                    };
+                }
+                Err(SYNC_INIT) => return Ok(self.get().unwrap()),
+                Err(_) => {
+                    while self.state.load(Ordering::Acquire) == SYNC_WRITING {
+                        core::hint::spin_loop();
+                    }
+                    // The winning thread's initializer may have failed, in which case
+                    // the static is uninitialized again and we race for it ourselves.
+                    if let Some(val) = self.get() {
+                        return Ok(val);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<T> Default for SyncLateStatic<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> core::ops::Deref for SyncLateStatic<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.get()
+            .expect("Dereference of late static before a value was assigned")
+    }
+}
+
+impl<T> Drop for SyncLateStatic<T> {
+    fn drop(&mut self) {
+        if self.has_value() {
+            unsafe {
+                core::ptr::drop_in_place((*self.val.get()).as_mut_ptr());
             }
         }
     }
@@ -183,4 +489,200 @@ mod tests {
             LateStatic::clear(&CLEAR_WITHOUT_VALUE);
         }
     }
+
+    static GET_OR_INIT_TEST: LateStatic<u32> = LateStatic::new();
+    #[test]
+    fn get_or_init() {
+        unsafe {
+            assert_eq!(*LateStatic::get_or_init(&GET_OR_INIT_TEST, || 42), 42);
+            assert_eq!(*LateStatic::get_or_init(&GET_OR_INIT_TEST, || 37), 42);
+        }
+    }
+
+    static GET_OR_TRY_INIT_TEST: LateStatic<u32> = LateStatic::new();
+    #[test]
+    fn get_or_try_init() {
+        unsafe {
+            assert_eq!(
+                LateStatic::get_or_try_init(&GET_OR_TRY_INIT_TEST, || Err::<u32, &str>("nope")),
+                Err("nope")
+            );
+            assert!(!LateStatic::has_value(&GET_OR_TRY_INIT_TEST));
+            assert_eq!(
+                LateStatic::get_or_try_init(&GET_OR_TRY_INIT_TEST, || Ok::<u32, &str>(42)),
+                Ok(&42)
+            );
+            assert_eq!(
+                LateStatic::get_or_try_init(&GET_OR_TRY_INIT_TEST, || Ok::<u32, &str>(37)),
+                Ok(&42)
+            );
+        }
+    }
+
+    static TRY_ASSIGN_TEST: LateStatic<u32> = LateStatic::new();
+    #[test]
+    fn try_assign() {
+        unsafe {
+            assert_eq!(TRY_ASSIGN_TEST.try_get(), None);
+            assert_eq!(TRY_ASSIGN_TEST.try_assign(42), Ok(()));
+            assert_eq!(TRY_ASSIGN_TEST.try_assign(37), Err(37));
+            assert_eq!(TRY_ASSIGN_TEST.try_get(), Some(&42));
+        }
+    }
+
+    static mut TRY_GET_MUT_TEST: LateStatic<u32> = LateStatic::new();
+    #[test]
+    fn try_get_mut() {
+        unsafe {
+            #[allow(static_mut_refs)]
+            let late = &mut TRY_GET_MUT_TEST;
+            assert_eq!(late.try_get_mut(), None);
+            late.try_assign(42).unwrap();
+            *late.try_get_mut().unwrap() = 37;
+            assert_eq!(late.try_get(), Some(&37));
+        }
+    }
+
+    static TAKE_TEST: LateStatic<u32> = LateStatic::new();
+    #[test]
+    fn take() {
+        unsafe {
+            assert_eq!(TAKE_TEST.take(), None);
+            TAKE_TEST.try_assign(42).unwrap();
+            assert_eq!(TAKE_TEST.take(), Some(42));
+            assert_eq!(TAKE_TEST.try_get(), None);
+        }
+    }
+
+    #[derive(Debug)]
+    struct DropFlag<'a>(&'a std::cell::Cell<bool>);
+    impl<'a> Drop for DropFlag<'a> {
+        fn drop(&mut self) {
+            self.0.set(true);
+        }
+    }
+
+    #[test]
+    fn drop_runs_inner_destructor() {
+        let dropped = std::cell::Cell::new(false);
+        let late: LateStatic<DropFlag> = LateStatic::new();
+        unsafe {
+            late.try_assign(DropFlag(&dropped)).unwrap();
+        }
+        drop(late);
+        assert!(dropped.get());
+    }
+
+    #[test]
+    fn drop_without_value_is_a_noop() {
+        let late: LateStatic<DropFlag> = LateStatic::new();
+        drop(late);
+    }
+
+    #[test]
+    fn take_runs_no_destructor_on_drop() {
+        let dropped = std::cell::Cell::new(false);
+        let late: LateStatic<DropFlag> = LateStatic::new();
+        let taken = unsafe {
+            late.try_assign(DropFlag(&dropped)).unwrap();
+            late.take()
+        };
+        drop(late);
+        assert!(!dropped.get());
+        drop(taken);
+        assert!(dropped.get());
+    }
+
+    static SYNC_ASSIGN_ONCE_TEST: SyncLateStatic<u32> = SyncLateStatic::new();
+    #[test]
+    fn sync_assign_once() {
+        assert!(!SYNC_ASSIGN_ONCE_TEST.has_value());
+        assert_eq!(SYNC_ASSIGN_ONCE_TEST.set(42), Ok(()));
+        assert!(SYNC_ASSIGN_ONCE_TEST.has_value());
+    }
+
+    static SYNC_ASSIGN_TWICE_TEST: SyncLateStatic<u32> = SyncLateStatic::new();
+    #[test]
+    fn sync_assign_twice() {
+        assert_eq!(SYNC_ASSIGN_TWICE_TEST.set(42), Ok(()));
+        assert_eq!(SYNC_ASSIGN_TWICE_TEST.set(37), Err(37));
+    }
+
+    static SYNC_GET_TEST: SyncLateStatic<u32> = SyncLateStatic::new();
+    #[test]
+    fn sync_get() {
+        assert_eq!(SYNC_GET_TEST.get(), None);
+        SYNC_GET_TEST.set(42).unwrap();
+        assert_eq!(SYNC_GET_TEST.get(), Some(&42));
+        assert_eq!(*SYNC_GET_TEST, 42);
+    }
+
+    static SYNC_CONCURRENT_ASSIGN_TEST: SyncLateStatic<u32> = SyncLateStatic::new();
+    #[test]
+    fn sync_concurrent_assign() {
+        let successes: u32 = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..8)
+                .map(|i| scope.spawn(move || SYNC_CONCURRENT_ASSIGN_TEST.set(i).is_ok()))
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap() as u32)
+                .sum()
+        });
+        assert_eq!(successes, 1);
+        assert!(SYNC_CONCURRENT_ASSIGN_TEST.has_value());
+    }
+
+    static SYNC_GET_OR_INIT_TEST: SyncLateStatic<u32> = SyncLateStatic::new();
+    #[test]
+    fn sync_get_or_init() {
+        assert_eq!(*SYNC_GET_OR_INIT_TEST.get_or_init(|| 42), 42);
+        assert_eq!(*SYNC_GET_OR_INIT_TEST.get_or_init(|| 37), 42);
+    }
+
+    static SYNC_GET_OR_TRY_INIT_TEST: SyncLateStatic<u32> = SyncLateStatic::new();
+    #[test]
+    fn sync_get_or_try_init() {
+        assert_eq!(
+            SYNC_GET_OR_TRY_INIT_TEST.get_or_try_init(|| Err::<u32, &str>("nope")),
+            Err("nope")
+        );
+        assert!(!SYNC_GET_OR_TRY_INIT_TEST.has_value());
+        assert_eq!(
+            SYNC_GET_OR_TRY_INIT_TEST.get_or_try_init(|| Ok::<u32, &str>(42)),
+            Ok(&42)
+        );
+        assert_eq!(
+            SYNC_GET_OR_TRY_INIT_TEST.get_or_try_init(|| Ok::<u32, &str>(37)),
+            Ok(&42)
+        );
+    }
+
+    static SYNC_GET_OR_INIT_UNWIND_TEST: SyncLateStatic<u32> = SyncLateStatic::new();
+    #[test]
+    fn sync_get_or_init_resets_state_on_unwind() {
+        let result = std::panic::catch_unwind(|| {
+            SYNC_GET_OR_INIT_UNWIND_TEST.get_or_init(|| panic!("initializer panicked"));
+        });
+        assert!(result.is_err());
+        assert!(!SYNC_GET_OR_INIT_UNWIND_TEST.has_value());
+        assert_eq!(*SYNC_GET_OR_INIT_UNWIND_TEST.get_or_init(|| 42), 42);
+    }
+
+    static SYNC_CONCURRENT_GET_OR_INIT_TEST: SyncLateStatic<u32> = SyncLateStatic::new();
+    #[test]
+    fn sync_concurrent_get_or_init() {
+        let results: Vec<u32> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..8)
+                .map(|i| {
+                    scope.spawn(move || *SYNC_CONCURRENT_GET_OR_INIT_TEST.get_or_init(|| i))
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect()
+        });
+        assert!(results.iter().all(|&val| val == results[0]));
+    }
 }